@@ -1,9 +1,10 @@
 use anyhow::Result;
+use chrono_tz::America::New_York;
 use std::time::Duration;
 use tokio_cron_scheduler::{Job, JobScheduler};
 use tracing::{error, info, warn};
 
-pub async fn run_example(mut sched: JobScheduler) -> Result<()> {
+pub async fn run_example(sched: JobScheduler) -> Result<()> {
     #[cfg(feature = "signal")]
     sched.shutdown_on_ctrl_c();
 
@@ -40,7 +41,7 @@ pub async fn run_example(mut sched: JobScheduler) -> Result<()> {
     let five_s_job_guid = five_s_job.guid();
     sched.add(five_s_job).await?;
 
-    let mut four_s_job_async = Job::new_async("1/4 * * * * *", |uuid, mut l| {
+    let mut four_s_job_async = Job::new_async("1/4 * * * * *", |uuid, l| {
         Box::pin(async move {
             info!("I run async every 4 seconds id {:?}", uuid);
             let next_tick = l.next_tick_for_job(uuid).await;
@@ -79,8 +80,34 @@ pub async fn run_example(mut sched: JobScheduler) -> Result<()> {
         .await?;
 
     let four_s_job_guid = four_s_job_async.guid();
+    // The scheduler's loop is tickless: once it's running, each add wakes it to recompute how
+    // long to sleep instead of waiting for a poll. It isn't started until sched.start() below,
+    // so this job is simply picked up on the loop's first pass.
     sched.add(four_s_job_async).await?;
 
+    // A job that panics should not take the rest of the scheduler down with it. The panic is
+    // caught and surfaced through the on_panic notification instead.
+    let mut panicky_job = Job::new("1/6 * * * * *", |uuid, _l| {
+        info!("{:?} about to panic on purpose", uuid);
+        panic!("nope, not today");
+    })
+    .unwrap();
+    panicky_job
+        .on_panic_notification_add(
+            &sched,
+            Box::new(|job_id, notification_id, panic_message| {
+                Box::pin(async move {
+                    error!(
+                        "Job {:?} panicked, notification {:?}, message: {}",
+                        job_id, notification_id, panic_message
+                    );
+                })
+            }),
+        )
+        .await?;
+    let panicky_job_guid = panicky_job.guid();
+    sched.add(panicky_job).await?;
+
     sched
         .add(
             Job::new("1/30 * * * * *", |uuid, _l| {
@@ -90,6 +117,16 @@ pub async fn run_example(mut sched: JobScheduler) -> Result<()> {
         )
         .await?;
 
+    // Fires at 9am New York time every day, DST included, instead of 9am UTC.
+    sched
+        .add(
+            Job::new_cron_tz("0 0 9 * * *", New_York, |uuid, _l| {
+                info!("{:?} Good morning New York id {:?}", chrono::Utc::now(), uuid);
+            })
+            .unwrap(),
+        )
+        .await?;
+
     info!(
         "Sched one shot for {:?}",
         chrono::Utc::now()
@@ -122,6 +159,24 @@ pub async fn run_example(mut sched: JobScheduler) -> Result<()> {
         )
         .await?;
 
+    // Chain a follow-up job: once "step one" completes successfully, the scheduler starts
+    // "step two" automatically instead of the caller wiring this up by hand in an
+    // on_done_notification callback.
+    let step_two = Job::new_one_shot(Duration::from_secs(0), |uuid, _l| {
+        info!("{:?} step two, running now that step one succeeded", uuid);
+    })
+    .unwrap();
+
+    let mut step_one = Job::new_one_shot(Duration::from_secs(5), |uuid, _l| {
+        info!("{:?} step one, about to hand off to step two", uuid);
+    })
+    .unwrap();
+    // on_success_add registers step_two with the scheduler itself (stopped, so it won't fire on
+    // its own 0s delay) and resumes it once step_one succeeds - step_two is never sched.add()'d
+    // directly.
+    step_one.on_success_add(&sched, &step_two).await?;
+    sched.add(step_one).await?;
+
     let jj = Job::new_repeated(Duration::from_secs(8), |_uuid, _l| {
         info!("I'm repeated every 8 seconds");
     })
@@ -145,9 +200,10 @@ pub async fn run_example(mut sched: JobScheduler) -> Result<()> {
     }
     tokio::time::sleep(Duration::from_secs(20)).await;
 
-    info!("Remove 4, 5, 7 and 8 sec jobs");
+    info!("Remove 4, 5, 6, 7 and 8 sec jobs");
     sched.remove(&five_s_job_guid).await?;
     sched.remove(&four_s_job_guid).await?;
+    sched.remove(&panicky_job_guid).await?;
     sched.remove(&jj_guid).await?;
     sched.remove(&jja_guid).await?;
 
@@ -158,6 +214,35 @@ pub async fn run_example(mut sched: JobScheduler) -> Result<()> {
     Ok(())
 }
 
+// tokio_cron_scheduler::JobScheduler always runs its tick loop via tokio::spawn, which requires
+// every stored job to be Send — so it can't host a job that captures an Rc, no matter which
+// Spawner it's built with. The crate ships a second, Rc/RefCell-based scheduler for that case:
+// LocalJobScheduler, driven entirely through tokio::task::spawn_local on this LocalSet, so
+// nothing here ever has to cross a thread.
+pub async fn run_example_unsend() -> Result<()> {
+    use std::rc::Rc;
+    use tokio_cron_scheduler::local::{LocalJob, LocalJobScheduler, UnsendSpawner};
+
+    let local = tokio::task::LocalSet::new();
+    local
+        .run_until(async {
+            let sched = LocalJobScheduler::new(UnsendSpawner);
+
+            let counter = Rc::new(std::cell::Cell::new(0));
+            let counter_clone = counter.clone();
+            let job_guid = sched.add(LocalJob::new_repeated(Duration::from_secs(3), move |uuid| {
+                counter_clone.set(counter_clone.get() + 1);
+                info!("{:?} !Send job ran, count {:?}", uuid, counter_clone.get());
+            }));
+
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            sched.remove(&job_guid);
+        })
+        .await;
+
+    Ok(())
+}
+
 fn main() {
     eprintln!("Should not be run on its own.");
 }
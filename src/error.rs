@@ -0,0 +1,27 @@
+use std::fmt;
+
+/// Errors returned by [`crate::Job`] and [`crate::JobScheduler`].
+#[derive(Debug)]
+pub enum JobSchedulerError {
+    /// The cron expression (or cron-with-timezone expression) could not be parsed.
+    CronParse(String),
+    /// No job exists in the scheduler with the given id.
+    JobNotFound,
+    /// The scheduler's internal tick loop has already been started.
+    AlreadyStarted,
+    /// The scheduler could not be shut down cleanly.
+    ShutdownError,
+}
+
+impl fmt::Display for JobSchedulerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobSchedulerError::CronParse(e) => write!(f, "invalid cron schedule: {e}"),
+            JobSchedulerError::JobNotFound => write!(f, "job not found"),
+            JobSchedulerError::AlreadyStarted => write!(f, "scheduler already started"),
+            JobSchedulerError::ShutdownError => write!(f, "error shutting down scheduler"),
+        }
+    }
+}
+
+impl std::error::Error for JobSchedulerError {}
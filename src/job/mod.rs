@@ -0,0 +1,453 @@
+use crate::error::JobSchedulerError;
+use crate::scheduler::JobScheduler;
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration as StdDuration;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+
+#[cfg(feature = "tz")]
+use chrono_tz::Tz;
+
+/// A future returned by an async job body or notification callback.
+pub type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// The kind of notification a job callback was registered for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobNotification {
+    /// The job has just started running.
+    Started,
+    /// The job finished running without panicking.
+    Done,
+    /// The job was removed from the scheduler.
+    Removed,
+}
+
+/// Callback invoked for [`JobNotification`]s, receiving the job id, the notification's own id
+/// (so it can remove itself), and the notification kind.
+pub type NotificationCallback = Box<dyn FnMut(Uuid, Uuid, JobNotification) -> BoxFuture + Send + Sync>;
+/// Callback invoked when a job's closure panics, receiving the job id, the notification id, and
+/// the panic message.
+pub type PanicCallback = Box<dyn FnMut(Uuid, Uuid, String) -> BoxFuture + Send + Sync>;
+
+enum JobAction {
+    Sync(Box<dyn FnMut(Uuid, JobScheduler) + Send + Sync>),
+    Async(Box<dyn FnMut(Uuid, JobScheduler) -> BoxFuture + Send + Sync>),
+}
+
+enum ScheduleKind {
+    Cron(Box<Schedule>),
+    #[cfg(feature = "tz")]
+    CronTz(Box<Schedule>, Tz),
+    OneShot(StdDuration),
+    Repeated(StdDuration),
+}
+
+impl ScheduleKind {
+    /// The job's first fire instant, relative to `now`.
+    fn first_tick_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            ScheduleKind::OneShot(d) => Some(now + to_chrono_duration(*d)),
+            _ => self.next_after(now),
+        }
+    }
+
+    /// The next fire instant after the job has just run at (approximately) `now`.
+    fn next_after(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            ScheduleKind::Cron(s) => s.after_owned(now).next(),
+            #[cfg(feature = "tz")]
+            ScheduleKind::CronTz(s, tz) => s
+                .after_owned(now.with_timezone(tz))
+                .next()
+                .map(|dt| dt.with_timezone(&Utc)),
+            ScheduleKind::OneShot(_) => None,
+            ScheduleKind::Repeated(d) => Some(now + to_chrono_duration(*d)),
+        }
+    }
+
+    fn is_one_shot(&self) -> bool {
+        matches!(self, ScheduleKind::OneShot(_))
+    }
+}
+
+fn to_chrono_duration(d: StdDuration) -> chrono::Duration {
+    chrono::Duration::from_std(d).unwrap_or(chrono::Duration::zero())
+}
+
+/// Everything about a job except the action it runs: schedule state, notifications, chaining.
+/// Kept behind its own mutex, separate from the job body's, so a notification callback (e.g.
+/// one that removes itself) never has to wait on a long-running job body, and vice versa.
+struct JobMeta {
+    schedule: ScheduleKind,
+    next_tick: Option<DateTime<Utc>>,
+    stopped: bool,
+    on_start: HashMap<Uuid, NotificationCallback>,
+    on_done: HashMap<Uuid, NotificationCallback>,
+    on_removed: HashMap<Uuid, NotificationCallback>,
+    on_panic: HashMap<Uuid, PanicCallback>,
+    /// Jobs to resume once this one completes successfully; see [`Job::on_success_add`].
+    on_success: Vec<Uuid>,
+    /// Notification ids removed while their own callback was mid-invocation; checked after the
+    /// callback returns so a "remove myself" call from inside the callback actually sticks.
+    pending_removed: HashSet<Uuid>,
+}
+
+/// A schedulable unit of work: a cron expression, a one-shot delay, or a fixed repeat interval,
+/// paired with a sync or async callback.
+///
+/// Cloning a `Job` is cheap and yields a handle to the same underlying job data.
+#[derive(Clone)]
+pub struct Job {
+    id: Uuid,
+    action: Arc<Mutex<JobAction>>,
+    meta: Arc<Mutex<JobMeta>>,
+}
+
+fn parse_schedule(expression: &str) -> Result<Schedule, JobSchedulerError> {
+    Schedule::from_str(expression).map_err(|e| JobSchedulerError::CronParse(e.to_string()))
+}
+
+impl Job {
+    fn from_parts(schedule: ScheduleKind, action: JobAction) -> Job {
+        Job {
+            id: Uuid::new_v4(),
+            action: Arc::new(Mutex::new(action)),
+            meta: Arc::new(Mutex::new(JobMeta {
+                schedule,
+                // Computed once the job is actually added to a scheduler (see
+                // `JobScheduler::add`/`Job::activate`), relative to that time.
+                next_tick: None,
+                stopped: false,
+                on_start: HashMap::new(),
+                on_done: HashMap::new(),
+                on_removed: HashMap::new(),
+                on_panic: HashMap::new(),
+                on_success: Vec::new(),
+                pending_removed: HashSet::new(),
+            })),
+        }
+    }
+
+    /// Creates a job firing on `schedule` (a six-field cron expression, UTC) running `run`
+    /// synchronously each time it fires.
+    pub fn new<F>(schedule: &str, run: F) -> Result<Job, JobSchedulerError>
+    where
+        F: FnMut(Uuid, JobScheduler) + Send + Sync + 'static,
+    {
+        let schedule = parse_schedule(schedule)?;
+        Ok(Job::from_parts(ScheduleKind::Cron(Box::new(schedule)), JobAction::Sync(Box::new(run))))
+    }
+
+    /// Like [`Job::new`], but `run` returns a future to await instead of running synchronously.
+    pub fn new_async<F>(schedule: &str, run: F) -> Result<Job, JobSchedulerError>
+    where
+        F: FnMut(Uuid, JobScheduler) -> BoxFuture + Send + Sync + 'static,
+    {
+        let schedule = parse_schedule(schedule)?;
+        Ok(Job::from_parts(ScheduleKind::Cron(Box::new(schedule)), JobAction::Async(Box::new(run))))
+    }
+
+    /// Like [`Job::new`], but `schedule` is interpreted in `tz` instead of UTC, so e.g.
+    /// `"0 0 9 * * *"` fires at 9am local time in that zone. DST gaps/overlaps are resolved by
+    /// `cron`'s timezone-aware iterator, which walks local datetimes through `tz` and skips any
+    /// that don't map to a valid instant.
+    #[cfg(feature = "tz")]
+    pub fn new_cron_tz<F>(schedule: &str, tz: Tz, run: F) -> Result<Job, JobSchedulerError>
+    where
+        F: FnMut(Uuid, JobScheduler) + Send + Sync + 'static,
+    {
+        let schedule = parse_schedule(schedule)?;
+        Ok(Job::from_parts(ScheduleKind::CronTz(Box::new(schedule), tz), JobAction::Sync(Box::new(run))))
+    }
+
+    /// Async counterpart to [`Job::new_cron_tz`].
+    #[cfg(feature = "tz")]
+    pub fn new_cron_tz_async<F>(schedule: &str, tz: Tz, run: F) -> Result<Job, JobSchedulerError>
+    where
+        F: FnMut(Uuid, JobScheduler) -> BoxFuture + Send + Sync + 'static,
+    {
+        let schedule = parse_schedule(schedule)?;
+        Ok(Job::from_parts(ScheduleKind::CronTz(Box::new(schedule), tz), JobAction::Async(Box::new(run))))
+    }
+
+    /// Creates a job that fires exactly once, `after` from when it's added to a scheduler.
+    pub fn new_one_shot<F>(after: StdDuration, run: F) -> Result<Job, JobSchedulerError>
+    where
+        F: FnMut(Uuid, JobScheduler) + Send + Sync + 'static,
+    {
+        Ok(Job::from_parts(ScheduleKind::OneShot(after), JobAction::Sync(Box::new(run))))
+    }
+
+    /// Async counterpart to [`Job::new_one_shot`].
+    pub fn new_one_shot_async<F>(after: StdDuration, run: F) -> Result<Job, JobSchedulerError>
+    where
+        F: FnMut(Uuid, JobScheduler) -> BoxFuture + Send + Sync + 'static,
+    {
+        Ok(Job::from_parts(ScheduleKind::OneShot(after), JobAction::Async(Box::new(run))))
+    }
+
+    /// Creates a job that fires every `every`, starting `every` from when it's added to a
+    /// scheduler.
+    pub fn new_repeated<F>(every: StdDuration, run: F) -> Result<Job, JobSchedulerError>
+    where
+        F: FnMut(Uuid, JobScheduler) + Send + Sync + 'static,
+    {
+        Ok(Job::from_parts(ScheduleKind::Repeated(every), JobAction::Sync(Box::new(run))))
+    }
+
+    /// Async counterpart to [`Job::new_repeated`].
+    pub fn new_repeated_async<F>(every: StdDuration, run: F) -> Result<Job, JobSchedulerError>
+    where
+        F: FnMut(Uuid, JobScheduler) -> BoxFuture + Send + Sync + 'static,
+    {
+        Ok(Job::from_parts(ScheduleKind::Repeated(every), JobAction::Async(Box::new(run))))
+    }
+
+    /// This job's unique id.
+    pub fn guid(&self) -> Uuid {
+        self.id
+    }
+
+    async fn ensure_registered(&self, sched: &JobScheduler) {
+        sched.ensure_registered_stopped(self).await;
+    }
+
+    /// Runs `cb` when this job starts. If the job hasn't been added to `sched` yet, it is
+    /// registered with a stopped marking so the notification has somewhere to live.
+    pub async fn on_start_notification_add(
+        &mut self,
+        sched: &JobScheduler,
+        cb: NotificationCallback,
+    ) -> Result<Uuid, JobSchedulerError> {
+        self.ensure_registered(sched).await;
+        let notification_id = Uuid::new_v4();
+        self.meta.lock().await.on_start.insert(notification_id, cb);
+        Ok(notification_id)
+    }
+
+    /// Removes a notification previously added with [`Job::on_start_notification_add`].
+    pub async fn on_start_notification_remove(&self, _sched: &JobScheduler, notification_id: &Uuid) -> bool {
+        remove_notification(&self.meta, notification_id, |meta| &mut meta.on_start).await
+    }
+
+    /// Runs `cb` when this job finishes running without panicking.
+    pub async fn on_done_notification_add(
+        &mut self,
+        sched: &JobScheduler,
+        cb: NotificationCallback,
+    ) -> Result<Uuid, JobSchedulerError> {
+        self.ensure_registered(sched).await;
+        let notification_id = Uuid::new_v4();
+        self.meta.lock().await.on_done.insert(notification_id, cb);
+        Ok(notification_id)
+    }
+
+    /// Removes a notification previously added with [`Job::on_done_notification_add`].
+    pub async fn on_done_notification_remove(&self, _sched: &JobScheduler, notification_id: &Uuid) -> bool {
+        remove_notification(&self.meta, notification_id, |meta| &mut meta.on_done).await
+    }
+
+    /// Runs `cb` when this job is removed from the scheduler.
+    pub async fn on_removed_notification_add(
+        &mut self,
+        sched: &JobScheduler,
+        cb: NotificationCallback,
+    ) -> Result<Uuid, JobSchedulerError> {
+        self.ensure_registered(sched).await;
+        let notification_id = Uuid::new_v4();
+        self.meta.lock().await.on_removed.insert(notification_id, cb);
+        Ok(notification_id)
+    }
+
+    /// Removes a notification previously added with [`Job::on_removed_notification_add`].
+    pub async fn on_removed_notification_remove(&self, _sched: &JobScheduler, notification_id: &Uuid) -> bool {
+        remove_notification(&self.meta, notification_id, |meta| &mut meta.on_removed).await
+    }
+
+    /// Runs `cb`, receiving the panic message, if this job's closure panics. The panic is caught
+    /// so the scheduler and every other job keep running.
+    pub async fn on_panic_notification_add(
+        &mut self,
+        sched: &JobScheduler,
+        cb: PanicCallback,
+    ) -> Result<Uuid, JobSchedulerError> {
+        self.ensure_registered(sched).await;
+        let notification_id = Uuid::new_v4();
+        self.meta.lock().await.on_panic.insert(notification_id, cb);
+        Ok(notification_id)
+    }
+
+    /// Removes a notification previously added with [`Job::on_panic_notification_add`].
+    pub async fn on_panic_notification_remove(&self, _sched: &JobScheduler, notification_id: &Uuid) -> bool {
+        let mut meta = self.meta.lock().await;
+        if meta.on_panic.remove(notification_id).is_some() {
+            true
+        } else {
+            meta.pending_removed.insert(*notification_id);
+            true
+        }
+    }
+
+    /// Chains `successor` to run once this job completes successfully. If `successor` isn't in
+    /// `sched` yet, it is registered right away, stopped, so it won't fire on its own; when this
+    /// job's body returns without panicking, the scheduler resumes it, with its schedule counted
+    /// from that moment (a zero-delay one-shot therefore runs right away). A `successor` already
+    /// in `sched` is not stopped. If `successor` is removed from the scheduler before this job
+    /// finishes, resuming it is a no-op.
+    pub async fn on_success_add(
+        &mut self,
+        sched: &JobScheduler,
+        successor: &Job,
+    ) -> Result<(), JobSchedulerError> {
+        self.ensure_registered(sched).await;
+        sched.ensure_registered_stopped(successor).await;
+        self.meta.lock().await.on_success.push(successor.guid());
+        Ok(())
+    }
+
+    pub(crate) async fn successors(&self) -> Vec<Uuid> {
+        self.meta.lock().await.on_success.clone()
+    }
+
+    pub(crate) async fn is_due(&self, now: DateTime<Utc>) -> bool {
+        let meta = self.meta.lock().await;
+        !meta.stopped && meta.next_tick.map(|t| t <= now).unwrap_or(false)
+    }
+
+    pub(crate) async fn next_tick(&self) -> Option<DateTime<Utc>> {
+        let meta = self.meta.lock().await;
+        if meta.stopped {
+            None
+        } else {
+            meta.next_tick
+        }
+    }
+
+    pub(crate) async fn is_one_shot(&self) -> bool {
+        self.meta.lock().await.schedule.is_one_shot()
+    }
+
+    /// Steps `next_tick` forward by one schedule occurrence from the tick that just fired.
+    pub(crate) async fn advance_next_tick(&self) {
+        let mut meta = self.meta.lock().await;
+        if let Some(due_tick) = meta.next_tick {
+            meta.next_tick = meta.schedule.next_after(due_tick);
+        }
+    }
+
+    pub(crate) async fn mark_stopped(&self) {
+        self.meta.lock().await.stopped = true;
+    }
+
+    /// Marks the job active and, the first time this is called, computes its first fire instant
+    /// relative to `now` (rather than at construction time). A second `add` of an
+    /// already-scheduled job leaves its progress untouched.
+    pub(crate) async fn activate(&self, now: DateTime<Utc>) {
+        let mut meta = self.meta.lock().await;
+        meta.stopped = false;
+        if meta.next_tick.is_none() {
+            meta.next_tick = meta.schedule.first_tick_after(now);
+        }
+    }
+
+    /// Invokes the job body, catching any panic so it can't take the rest of the scheduler down.
+    /// Returns `Ok(())` on success, or `Err(message)` with the panic payload as text.
+    pub(crate) async fn run(&self, sched: JobScheduler) -> Result<(), String> {
+        let id = self.id;
+        let mut action = self.action.lock().await;
+        match &mut *action {
+            JobAction::Sync(run) => {
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| run(id, sched))).map_err(panic_message)
+            }
+            JobAction::Async(run) => {
+                use futures::FutureExt;
+                let built =
+                    std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| run(id, sched))).map_err(panic_message)?;
+                std::panic::AssertUnwindSafe(built).catch_unwind().await.map_err(panic_message)
+            }
+        }
+    }
+
+    pub(crate) async fn notify(&self, kind: JobNotification) {
+        let ids: Vec<Uuid> = {
+            let meta = self.meta.lock().await;
+            let map = notification_map(&meta, kind);
+            map.keys().copied().collect()
+        };
+        for notification_id in ids {
+            let taken = {
+                let mut meta = self.meta.lock().await;
+                notification_map_mut(&mut meta, kind).remove(&notification_id)
+            };
+            let Some(mut cb) = taken else { continue };
+            cb(self.id, notification_id, kind).await;
+            let mut meta = self.meta.lock().await;
+            if meta.pending_removed.remove(&notification_id) {
+                continue;
+            }
+            notification_map_mut(&mut meta, kind).insert(notification_id, cb);
+        }
+    }
+
+    pub(crate) async fn notify_panic(&self, message: &str) {
+        let ids: Vec<Uuid> = self.meta.lock().await.on_panic.keys().copied().collect();
+        for notification_id in ids {
+            let taken = self.meta.lock().await.on_panic.remove(&notification_id);
+            let Some(mut cb) = taken else { continue };
+            cb(self.id, notification_id, message.to_string()).await;
+            let mut meta = self.meta.lock().await;
+            if meta.pending_removed.remove(&notification_id) {
+                continue;
+            }
+            meta.on_panic.insert(notification_id, cb);
+        }
+    }
+}
+
+fn notification_map(meta: &JobMeta, kind: JobNotification) -> &HashMap<Uuid, NotificationCallback> {
+    match kind {
+        JobNotification::Started => &meta.on_start,
+        JobNotification::Done => &meta.on_done,
+        JobNotification::Removed => &meta.on_removed,
+    }
+}
+
+fn notification_map_mut(meta: &mut JobMeta, kind: JobNotification) -> &mut HashMap<Uuid, NotificationCallback> {
+    match kind {
+        JobNotification::Started => &mut meta.on_start,
+        JobNotification::Done => &mut meta.on_done,
+        JobNotification::Removed => &mut meta.on_removed,
+    }
+}
+
+async fn remove_notification(
+    meta: &Arc<Mutex<JobMeta>>,
+    notification_id: &Uuid,
+    map: impl Fn(&mut JobMeta) -> &mut HashMap<Uuid, NotificationCallback>,
+) -> bool {
+    let mut meta = meta.lock().await;
+    if map(&mut meta).remove(notification_id).is_some() {
+        true
+    } else {
+        // Might be mid-invocation (e.g. removing itself from within its own callback); mark it
+        // so `Job::notify` drops it instead of reinserting once the callback returns.
+        meta.pending_removed.insert(*notification_id);
+        true
+    }
+}
+
+fn panic_message(payload: Box<dyn std::any::Any + Send>) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "job panicked with a non-string payload".to_string()
+    }
+}
@@ -0,0 +1,16 @@
+//! A Tokio-based cron/interval job scheduler with panic isolation, timezone-aware schedules, an
+//! injectable clock for deterministic tests, a pluggable spawner, and job chaining via
+//! [`Job::on_success_add`].
+
+mod error;
+mod job;
+pub mod local;
+mod scheduler;
+mod spawner;
+mod time;
+
+pub use error::JobSchedulerError;
+pub use job::{BoxFuture, Job, JobNotification, NotificationCallback, PanicCallback};
+pub use scheduler::JobScheduler;
+pub use spawner::{SpawnedFuture, Spawner, TokioSpawner};
+pub use time::{ChronoClock, MockClock, TimeProvider};
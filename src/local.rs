@@ -0,0 +1,103 @@
+//! A second, smaller scheduler for jobs whose bodies capture `!Send` state (an `Rc`, a non-`Send`
+//! client handle) that [`crate::JobScheduler`] cannot run no matter which [`crate::Spawner`] it's
+//! built with — the job map [`crate::JobScheduler`]'s tick loop reads is shared across a
+//! `tokio::spawn`'d task, and `tokio::spawn` requires everything it touches, including every
+//! stored job, to be `Send`. That's a static property of the closure's type, not something a
+//! [`crate::Spawner`] implementation can waive, so [`UnsendSpawner`] deliberately does *not*
+//! implement [`crate::Spawner`] — `JobScheduler::new_with_spawner(UnsendSpawner)` does not
+//! compile, and is not meant to. [`LocalJob`]/[`LocalJobScheduler`] are an unrelated pair of types
+//! instead, built around `Rc`/`RefCell` and run exclusively via `tokio::task::spawn_local` on a
+//! `tokio::task::LocalSet`, so nothing here is ever required to cross a thread.
+//!
+//! This is a narrower scheduler than [`crate::JobScheduler`], not a drop-in replacement for it:
+//! [`LocalJob`] only supports a fixed repeat interval ([`LocalJob::new_repeated`]) and has no cron
+//! schedules, notifications, panic isolation, or [`crate::Job::on_success_add`]-style chaining.
+//! Reach for it only when a job body genuinely can't be made `Send`; otherwise use
+//! [`crate::Job`]/[`crate::JobScheduler`].
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Marker type passed to [`LocalJobScheduler::new`]. It does *not* implement [`crate::Spawner`]
+/// and cannot be used with [`crate::JobScheduler::new_with_spawner`] — see the module docs.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct UnsendSpawner;
+
+struct LocalJobState {
+    every: Duration,
+    action: RefCell<Box<dyn FnMut(Uuid)>>,
+    stopped: Rc<Cell<bool>>,
+}
+
+/// A job that may capture `!Send` state. Only runnable via [`LocalJobScheduler`].
+pub struct LocalJob {
+    id: Uuid,
+    state: Rc<LocalJobState>,
+}
+
+impl LocalJob {
+    /// Creates a job that runs `run` every `every`, starting `every` from when it's added.
+    pub fn new_repeated(every: Duration, run: impl FnMut(Uuid) + 'static) -> LocalJob {
+        LocalJob {
+            id: Uuid::new_v4(),
+            state: Rc::new(LocalJobState {
+                every,
+                action: RefCell::new(Box::new(run)),
+                stopped: Rc::new(Cell::new(false)),
+            }),
+        }
+    }
+
+    /// This job's unique id.
+    pub fn guid(&self) -> Uuid {
+        self.id
+    }
+}
+
+/// Runs [`LocalJob`]s via [`UnsendSpawner`].
+///
+/// Must be constructed and used from inside a `tokio::task::LocalSet::run_until` (or an
+/// equivalent current-thread context) for the whole lifetime of its jobs.
+#[derive(Clone)]
+pub struct LocalJobScheduler {
+    jobs: Rc<RefCell<HashMap<Uuid, Rc<LocalJobState>>>>,
+}
+
+impl LocalJobScheduler {
+    /// Creates a scheduler backed by `spawner`. `spawner` only exists to make the call site read
+    /// like [`crate::JobScheduler::new_with_spawner`]; there is only one way to drive a
+    /// `LocalJob` today.
+    pub fn new(_spawner: UnsendSpawner) -> LocalJobScheduler {
+        LocalJobScheduler {
+            jobs: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    /// Adds `job` and starts it firing on its own interval.
+    pub fn add(&self, job: LocalJob) -> Uuid {
+        let id = job.id;
+        self.jobs.borrow_mut().insert(id, job.state.clone());
+
+        let state = job.state;
+        tokio::task::spawn_local(async move {
+            loop {
+                tokio::time::sleep(state.every).await;
+                if state.stopped.get() {
+                    return;
+                }
+                (state.action.borrow_mut())(id);
+            }
+        });
+        id
+    }
+
+    /// Stops `id`'s spawned loop and forgets the job.
+    pub fn remove(&self, id: &Uuid) {
+        if let Some(state) = self.jobs.borrow_mut().remove(id) {
+            state.stopped.set(true);
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use crate::error::JobSchedulerError;
+use crate::job::{BoxFuture, Job, JobNotification};
+use crate::spawner::{Spawner, SpawnedFuture, TokioSpawner};
+use crate::time::{ChronoClock, TimeProvider};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration as StdDuration;
+use tokio::sync::{Notify, RwLock};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+type ShutdownHandler = Box<dyn Fn() -> BoxFuture + Send + Sync>;
+
+struct SchedulerInner {
+    jobs: RwLock<HashMap<Uuid, Job>>,
+    clock: Arc<dyn TimeProvider>,
+    spawner: Arc<dyn Spawner>,
+    wake: Notify,
+    /// Count of due-job bodies currently spawned and still running, so [`wait_until_idle`] can
+    /// tell a [`crate::MockClock`] when it's safe to report an `advance` as fully settled without
+    /// making the tick loop itself wait on them (which would delay picking up the *next* due job).
+    outstanding: AtomicUsize,
+    idle: Notify,
+    shutdown_flag: AtomicBool,
+    shutdown_handler: StdMutex<Option<ShutdownHandler>>,
+    loop_handle: StdMutex<Option<JoinHandle<()>>>,
+}
+
+/// Runs [`Job`]s on their schedules.
+///
+/// The tick loop is tickless: it tracks every job's next-fire instant and sleeps exactly until
+/// the nearest one, waking early whenever [`JobScheduler::add`]/[`JobScheduler::remove`] (or a
+/// [`crate::MockClock::advance`]) changes what's due.
+#[derive(Clone)]
+pub struct JobScheduler {
+    inner: Arc<SchedulerInner>,
+}
+
+impl JobScheduler {
+    /// Creates a scheduler backed by the system clock and `tokio::spawn`.
+    pub async fn new() -> Result<JobScheduler, JobSchedulerError> {
+        JobScheduler::build(Arc::new(ChronoClock), Arc::new(TokioSpawner))
+    }
+
+    /// Creates a scheduler driven by `clock` instead of the system clock — see [`crate::MockClock`]
+    /// for deterministic tests.
+    pub async fn new_with_clock(clock: impl TimeProvider + 'static) -> Result<JobScheduler, JobSchedulerError> {
+        JobScheduler::build(Arc::new(clock), Arc::new(TokioSpawner))
+    }
+
+    /// Creates a scheduler that launches job and notification futures via `spawner` instead of
+    /// the default `tokio::spawn`.
+    pub async fn new_with_spawner(spawner: impl Spawner + 'static) -> Result<JobScheduler, JobSchedulerError> {
+        JobScheduler::build(Arc::new(ChronoClock), Arc::new(spawner))
+    }
+
+    fn build(clock: Arc<dyn TimeProvider>, spawner: Arc<dyn Spawner>) -> Result<JobScheduler, JobSchedulerError> {
+        Ok(JobScheduler {
+            inner: Arc::new(SchedulerInner {
+                jobs: RwLock::new(HashMap::new()),
+                clock,
+                spawner,
+                wake: Notify::new(),
+                outstanding: AtomicUsize::new(0),
+                idle: Notify::new(),
+                shutdown_flag: AtomicBool::new(false),
+                shutdown_handler: StdMutex::new(None),
+                loop_handle: StdMutex::new(None),
+            }),
+        })
+    }
+
+    /// Adds `job` and makes it eligible to fire on its own schedule.
+    pub async fn add(&self, job: Job) -> Result<Uuid, JobSchedulerError> {
+        let id = job.guid();
+        job.activate(self.inner.clock.now()).await;
+        self.inner.jobs.write().await.insert(id, job);
+        self.inner.wake.notify_one();
+        Ok(id)
+    }
+
+    /// Used by `Job::on_*_notification_add`: if `job` isn't in the scheduler yet, it's registered
+    /// with a stopped marking so the notification has somewhere to live.
+    pub(crate) async fn ensure_registered_stopped(&self, job: &Job) {
+        let already_registered = self.inner.jobs.read().await.contains_key(&job.guid());
+        if !already_registered {
+            job.mark_stopped().await;
+            self.inner.jobs.write().await.insert(job.guid(), job.clone());
+        }
+    }
+
+    /// Reactivates a stopped job and wakes the tick loop so it recomputes its wakeup. A job with
+    /// no fire instant yet gets one from its own schedule, counted from now, so only a zero-delay
+    /// one-shot runs right away. Used to resume a job chained via [`Job::on_success_add`] once its
+    /// predecessor succeeds; a no-op if `id` isn't (or is no longer) in the scheduler.
+    pub(crate) async fn resume(&self, id: &Uuid) {
+        if let Some(job) = self.inner.jobs.read().await.get(id).cloned() {
+            job.activate(self.inner.clock.now()).await;
+            self.inner.wake.notify_one();
+        }
+    }
+
+    /// Removes a job, running its on-removed notifications.
+    pub async fn remove(&self, id: &Uuid) -> Result<(), JobSchedulerError> {
+        let job = self.inner.jobs.write().await.remove(id);
+        match job {
+            Some(job) => {
+                job.notify(JobNotification::Removed).await;
+                self.inner.wake.notify_one();
+                Ok(())
+            }
+            None => Err(JobSchedulerError::JobNotFound),
+        }
+    }
+
+    /// The next instant `id` is due to fire, or `None` if it's stopped or has no further fire
+    /// instant. Returns [`JobSchedulerError::JobNotFound`] if `id` isn't in the scheduler.
+    pub async fn next_tick_for_job(&self, id: Uuid) -> Result<Option<chrono::DateTime<Utc>>, JobSchedulerError> {
+        let job = self.inner.jobs.read().await.get(&id).cloned();
+        match job {
+            Some(job) => Ok(job.next_tick().await),
+            None => Err(JobSchedulerError::JobNotFound),
+        }
+    }
+
+    /// Starts the tick loop. Returns [`JobSchedulerError::AlreadyStarted`] if it's already
+    /// running.
+    pub async fn start(&self) -> Result<(), JobSchedulerError> {
+        let mut handle_guard = self.inner.loop_handle.lock().expect("loop_handle mutex poisoned");
+        if handle_guard.is_some() {
+            return Err(JobSchedulerError::AlreadyStarted);
+        }
+        let inner = self.inner.clone();
+        *handle_guard = Some(tokio::spawn(run_loop(inner)));
+        Ok(())
+    }
+
+    /// Sets a callback run once [`JobScheduler::shutdown`] has stopped the tick loop.
+    pub fn set_shutdown_handler(&self, handler: ShutdownHandler) {
+        *self.inner.shutdown_handler.lock().expect("shutdown_handler mutex poisoned") = Some(handler);
+    }
+
+    /// Spawns a task that calls [`JobScheduler::shutdown`] on Ctrl-C. Requires the `signal`
+    /// feature.
+    #[cfg(feature = "signal")]
+    pub fn shutdown_on_ctrl_c(&self) {
+        let sched = self.clone();
+        tokio::spawn(async move {
+            if tokio::signal::ctrl_c().await.is_ok() {
+                let _ = sched.shutdown().await;
+            }
+        });
+    }
+
+    /// Stops the tick loop and runs the shutdown handler, if any.
+    pub async fn shutdown(&self) -> Result<(), JobSchedulerError> {
+        self.inner.shutdown_flag.store(true, Ordering::SeqCst);
+        self.inner.wake.notify_one();
+        let handle = self
+            .inner
+            .loop_handle
+            .lock()
+            .expect("loop_handle mutex poisoned")
+            .take();
+        if let Some(handle) = handle {
+            handle.await.map_err(|_| JobSchedulerError::ShutdownError)?;
+        }
+        let handler = self
+            .inner
+            .shutdown_handler
+            .lock()
+            .expect("shutdown_handler mutex poisoned")
+            .take();
+        if let Some(handler) = handler {
+            handler().await;
+        }
+        Ok(())
+    }
+}
+
+async fn run_loop(inner: Arc<SchedulerInner>) {
+    while !inner.shutdown_flag.load(Ordering::SeqCst) {
+        drain_due_jobs(&inner).await;
+        // Reporting "settled" has to wait for the due jobs just dispatched to actually finish
+        // running, not just for them to have been spawned - but that wait happens in its own
+        // task so it never delays this loop from going back to sleep (or waking again for the
+        // next due job) in the meantime.
+        if let Some(settled) = inner.clock.settled_notify() {
+            let inner = inner.clone();
+            tokio::spawn(async move {
+                wait_until_idle(&inner).await;
+                settled.notify_one();
+            });
+        }
+        if inner.shutdown_flag.load(Ordering::SeqCst) {
+            break;
+        }
+        wait_for_next_wakeup(&inner).await;
+    }
+}
+
+async fn wait_until_idle(inner: &Arc<SchedulerInner>) {
+    loop {
+        if inner.outstanding.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        let notified = inner.idle.notified();
+        if inner.outstanding.load(Ordering::SeqCst) == 0 {
+            return;
+        }
+        notified.await;
+    }
+}
+
+async fn drain_due_jobs(inner: &Arc<SchedulerInner>) {
+    loop {
+        if inner.shutdown_flag.load(Ordering::SeqCst) {
+            return;
+        }
+        let now = inner.clock.now();
+        let jobs: Vec<Job> = inner.jobs.read().await.values().cloned().collect();
+        let mut due = Vec::new();
+        for job in &jobs {
+            if job.is_due(now).await {
+                due.push(job.clone());
+            }
+        }
+        if due.is_empty() {
+            return;
+        }
+        for job in due {
+            // Advancing next_tick has to happen here, synchronously, so a job that's still due
+            // after this round (e.g. a MockClock jump spanning several occurrences) isn't handed
+            // off twice. Everything else about running the job - the body itself, notifications,
+            // chained successors - is dispatched concurrently below so a slow job never delays
+            // any other job that became due at the same tick.
+            job.advance_next_tick().await;
+            spawn_due_job(inner, job);
+        }
+    }
+}
+
+fn spawn_due_job(inner: &Arc<SchedulerInner>, job: Job) {
+    inner.outstanding.fetch_add(1, Ordering::SeqCst);
+    let inner_for_fut = inner.clone();
+    let fut: SpawnedFuture = Box::pin(async move {
+        let inner = inner_for_fut;
+        let is_one_shot = job.is_one_shot().await;
+        let sched = JobScheduler { inner: inner.clone() };
+
+        job.notify(JobNotification::Started).await;
+        match job.run(sched.clone()).await {
+            Ok(()) => {
+                job.notify(JobNotification::Done).await;
+                for successor_id in job.successors().await {
+                    sched.resume(&successor_id).await;
+                }
+            }
+            Err(message) => job.notify_panic(&message).await,
+        }
+
+        if is_one_shot {
+            let removed = inner.jobs.write().await.remove(&job.guid());
+            if removed.is_some() {
+                job.notify(JobNotification::Removed).await;
+            }
+        }
+
+        if inner.outstanding.fetch_sub(1, Ordering::SeqCst) == 1 {
+            inner.idle.notify_waiters();
+        }
+    });
+    // Fire-and-forget: awaiting the handle here would serialize every due job through this loop,
+    // defeating the point of spawning them out in the first place.
+    let _handle = inner.spawner.spawn(fut);
+}
+
+async fn wait_for_next_wakeup(inner: &Arc<SchedulerInner>) {
+    if let Some(manual_wake) = inner.clock.wake_notify() {
+        // A manually-driven clock (e.g. MockClock): only an explicit advance() or an add/remove
+        // moves us forward, never a real sleep.
+        tokio::select! {
+            _ = manual_wake.notified() => {}
+            _ = inner.wake.notified() => {}
+        }
+        return;
+    }
+
+    let now = inner.clock.now();
+    let jobs: Vec<Job> = inner.jobs.read().await.values().cloned().collect();
+    let mut next_deadline: Option<DateTime<Utc>> = None;
+    for job in &jobs {
+        if let Some(tick) = job.next_tick().await {
+            next_deadline = Some(next_deadline.map_or(tick, |d| d.min(tick)));
+        }
+    }
+
+    match next_deadline {
+        Some(deadline) => {
+            let remaining = (deadline - now).to_std().unwrap_or(StdDuration::from_secs(0));
+            tokio::select! {
+                _ = tokio::time::sleep(remaining) => {}
+                _ = inner.wake.notified() => {}
+            }
+        }
+        None => inner.wake.notified().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JobScheduler;
+    use crate::{Job, MockClock};
+    use chrono::{TimeZone, Utc};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration as StdDuration;
+
+    // Drives the scheduler against a MockClock instead of sleeping on the wall clock, so the
+    // fire count can be asserted deterministically. The clock starts on a whole second so the
+    // "1/5 * * * * *" job's fire instants land at known offsets (+1, +6, +11, +16) instead of
+    // depending on whatever second the test happened to start on.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_schedule_with_mock_clock() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let clock = MockClock::starting_at(start);
+        let scheduler = JobScheduler::new_with_clock(clock.clone()).await.unwrap();
+
+        let run_count = Arc::new(AtomicUsize::new(0));
+        let run_count_clone = run_count.clone();
+        scheduler
+            .add(
+                Job::new_async("1/5 * * * * *", move |_, _| {
+                    let run_count = run_count_clone.clone();
+                    Box::pin(async move {
+                        run_count.fetch_add(1, Ordering::SeqCst);
+                    })
+                })
+                .unwrap(),
+            )
+            .await
+            .expect("Should be able to add a job");
+
+        scheduler.start().await.unwrap();
+
+        // Jump 19 simulated seconds forward; a "1/5 * * * * *" job starting at :00 should have
+        // fired at :01, :06, :11 and :16 — 4 times.
+        clock.advance(StdDuration::from_secs(19)).await;
+
+        assert_eq!(run_count.load(Ordering::SeqCst), 4);
+    }
+}
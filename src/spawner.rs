@@ -0,0 +1,28 @@
+use std::future::Future;
+use std::pin::Pin;
+use tokio::task::JoinHandle;
+
+/// A boxed, `Send` job or notification future, ready to be handed to a [`Spawner`].
+pub type SpawnedFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Abstracts over *how* the scheduler launches a job/notification future, so the crate isn't
+/// hard-wired to `tokio::spawn` and the global multi-threaded runtime.
+///
+/// This only covers `Send` futures. Jobs that need to capture `!Send` state (an `Rc`, a
+/// non-`Send` client handle) can't go through this trait at all, since `Send`-ness is a static
+/// property of the boxed future's type, not something a runtime check can waive — see
+/// [`crate::local`] for that case instead.
+pub trait Spawner: Send + Sync + 'static {
+    /// Launches `fut` and returns a handle to it.
+    fn spawn(&self, fut: SpawnedFuture) -> JoinHandle<()>;
+}
+
+/// The default [`Spawner`], backed by `tokio::spawn` on the ambient runtime.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TokioSpawner;
+
+impl Spawner for TokioSpawner {
+    fn spawn(&self, fut: SpawnedFuture) -> JoinHandle<()> {
+        tokio::spawn(fut)
+    }
+}
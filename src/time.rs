@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Notify, RwLock};
+
+/// A source of "now" for the scheduler's tick loop.
+///
+/// The default [`ChronoClock`] just calls [`Utc::now`]. Tests can swap in a [`MockClock`] so a
+/// job's fire count can be asserted after advancing simulated time instead of sleeping on the
+/// wall clock.
+pub trait TimeProvider: Send + Sync {
+    /// The current time, as the tick loop sees it.
+    fn now(&self) -> DateTime<Utc>;
+
+    /// A [`Notify`] the tick loop should wait on in addition to its normal timer, so that a
+    /// manually-driven clock (like [`MockClock`]) can wake the loop when time is advanced.
+    /// Real clocks don't need this: the loop's own timed sleep already wakes it on time.
+    fn wake_notify(&self) -> Option<Arc<Notify>> {
+        None
+    }
+
+    /// A [`Notify`] the tick loop should ping once it has drained every currently-due job and
+    /// gone back to waiting, so [`MockClock::advance`] can report back deterministically instead
+    /// of guessing how long draining will take.
+    fn settled_notify(&self) -> Option<Arc<Notify>> {
+        None
+    }
+}
+
+/// The default [`TimeProvider`], backed by the system clock.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ChronoClock;
+
+impl TimeProvider for ChronoClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+struct MockClockState {
+    now: DateTime<Utc>,
+}
+
+/// A manually-driven clock for deterministic tests.
+///
+/// [`MockClock::advance`] moves simulated time forward and wakes the scheduler's tick loop so it
+/// re-evaluates which jobs are due, without the test ever sleeping on the real clock.
+#[derive(Clone)]
+pub struct MockClock {
+    state: Arc<RwLock<MockClockState>>,
+    wake: Arc<Notify>,
+    settled: Arc<Notify>,
+}
+
+impl MockClock {
+    /// Creates a clock starting at the current wall-clock instant.
+    pub fn new() -> Self {
+        Self::starting_at(Utc::now())
+    }
+
+    /// Creates a clock starting at a fixed, caller-chosen instant.
+    pub fn starting_at(start: DateTime<Utc>) -> Self {
+        MockClock {
+            state: Arc::new(RwLock::new(MockClockState { now: start })),
+            wake: Arc::new(Notify::new()),
+            settled: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Moves simulated time forward by `duration` and waits for the scheduler to finish
+    /// processing every job that became due as a result, so assertions right after `advance`
+    /// observe the complete effect of the jump.
+    pub async fn advance(&self, duration: Duration) {
+        {
+            let mut state = self.state.write().await;
+            state.now += chrono::Duration::from_std(duration).expect("duration fits in range");
+        }
+        self.wake.notify_one();
+        // Give the tick loop a chance to run and signal back once it has drained every job due
+        // at or before the new "now" and gone back to waiting.
+        let settled = self.settled.notified();
+        tokio::task::yield_now().await;
+        tokio::select! {
+            _ = settled => {}
+            _ = tokio::time::sleep(Duration::from_millis(200)) => {}
+        }
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TimeProvider for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.state
+            .try_read()
+            .map(|s| s.now)
+            .unwrap_or_else(|_| Utc::now())
+    }
+
+    fn wake_notify(&self) -> Option<Arc<Notify>> {
+        Some(self.wake.clone())
+    }
+
+    fn settled_notify(&self) -> Option<Arc<Notify>> {
+        Some(self.settled.clone())
+    }
+}